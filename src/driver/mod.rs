@@ -0,0 +1,212 @@
+//! Shared timer driver
+//!
+//! Instead of backing every [Timer](../oneshot/type.Timer.html) with its own OS
+//! timer, the driver multiplexes all registered timeouts onto a single
+//! [Oneshot](../oneshot/trait.Oneshot.html) using a hierarchical
+//! [TimerWheel](wheel/struct.TimerWheel.html). This scales to thousands of
+//! concurrent timeouts without thousands of file descriptors.
+//!
+//! Spawn [Driver::run](struct.Driver.html#method.run) on an executor, then hand
+//! out [Driver::timer](struct.Driver.html#method.timer) futures; each registers
+//! a wheel entry and parks until its deadline rather than owning an fd.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::{task, time};
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::oneshot::{Oneshot, Timer as OsTimer};
+
+pub mod wheel;
+
+use wheel::{TimerWheel, Token};
+
+struct Inner {
+    wheel: TimerWheel,
+    epoch: Instant,
+    ///Waker of the driver task, so a fresh registration can re-arm it.
+    driver: Option<task::Waker>,
+}
+
+impl Inner {
+    fn now_ms(&self) -> u64 {
+        wheel::as_millis(self.epoch.elapsed())
+    }
+}
+
+///Handle to a shared timer driver.
+///
+///Cheap to clone; all clones refer to the same wheel.
+#[derive(Clone)]
+pub struct Driver {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Driver {
+    ///Creates a new driver with its epoch anchored at the current instant.
+    pub fn new() -> Self {
+        let epoch = Instant::now();
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                wheel: TimerWheel::new(0),
+                epoch,
+                driver: None,
+            })),
+        }
+    }
+
+    ///Registers a timeout against the driver, returning a future that resolves
+    ///once `timeout` elapses.
+    pub fn timer(&self, timeout: time::Duration) -> WheelTimer {
+        WheelTimer {
+            inner: self.inner.clone(),
+            delay: wheel::as_millis(timeout),
+            deadline: None,
+            token: None,
+        }
+    }
+
+    ///Runs the driver event loop.
+    ///
+    ///Arms exactly one OS timer for the nearest pending deadline; on fire it
+    ///advances the wheel, wakes the entries whose time has come, and re-arms for
+    ///the next nearest deadline. The returned future never resolves and should
+    ///be spawned for the lifetime of the driver.
+    pub fn run(&self) -> DriverTask {
+        DriverTask {
+            inner: self.inner.clone(),
+            timer: None,
+            armed: None,
+        }
+    }
+}
+
+impl Default for Driver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Future resolving when a driver-registered timeout elapses.
+pub struct WheelTimer {
+    inner: Arc<Mutex<Inner>>,
+    ///Configured timeout, consumed to anchor `deadline` on first poll.
+    delay: u64,
+    ///Absolute deadline in epoch ms, fixed on first poll.
+    deadline: Option<u64>,
+    ///Wheel token, set once the timer is armed on first poll.
+    token: Option<Token>,
+}
+
+impl Drop for WheelTimer {
+    fn drop(&mut self) {
+        if let Some(token) = self.token.take() {
+            let mut inner = self.inner.lock().expect("lock driver");
+            inner.wheel.remove(token);
+        }
+    }
+}
+
+impl Future for WheelTimer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        let mut inner = self.inner.lock().expect("lock driver");
+
+        let now = inner.now_ms();
+        //First poll anchors the absolute deadline; it stays fixed afterwards.
+        let deadline = *self.deadline.get_or_insert_with(|| now.saturating_add(self.delay));
+
+        //Expiry is decided by the clock, not by which waker fired, so spurious
+        //wakeups are harmless.
+        if now >= deadline {
+            if let Some(token) = self.token.take() {
+                inner.wheel.remove(token);
+            }
+            return task::Poll::Ready(());
+        }
+
+        //Already registered: the deadline is fixed, so just refresh the waker in
+        //place. Re-inserting on every poll would make a fleet of timers O(n) per
+        //poll and needlessly re-arm the driver.
+        if let Some(token) = self.token {
+            if inner.wheel.update_waker(token, ctx.waker()) {
+                return task::Poll::Pending;
+            }
+            self.token = None;
+        }
+
+        //First poll (or a lost entry): register and re-arm the driver so it can
+        //account for a possibly nearer deadline.
+        let token = inner.wheel.insert(deadline, ctx.waker().clone());
+        if let Some(driver) = inner.driver.take() {
+            driver.wake();
+        }
+        self.token = Some(token);
+        task::Poll::Pending
+    }
+}
+
+///Driver event-loop future produced by [Driver::run](struct.Driver.html#method.run).
+pub struct DriverTask {
+    inner: Arc<Mutex<Inner>>,
+    timer: Option<OsTimer>,
+    ///Absolute deadline the OS timer is currently armed for, in epoch ms.
+    armed: Option<u64>,
+}
+
+impl Future for DriverTask {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut task::Context) -> task::Poll<Self::Output> {
+        loop {
+            let mut wakers = Vec::new();
+            let next = {
+                let mut inner = self.inner.lock().expect("lock driver");
+                inner.driver = Some(ctx.waker().clone());
+
+                let now = inner.now_ms();
+                inner.wheel.advance(now, &mut wakers);
+                inner.wheel.next_timeout()
+            };
+
+            for waker in wakers {
+                waker.wake();
+            }
+
+            let next = match next {
+                //Nothing pending: park until a registration re-arms us.
+                None => {
+                    self.timer = None;
+                    self.armed = None;
+                    return task::Poll::Pending;
+                }
+                Some(next) => next,
+            };
+
+            let target = {
+                let inner = self.inner.lock().expect("lock driver");
+                inner.now_ms().saturating_add(next)
+            };
+
+            if self.armed != Some(target) {
+                self.timer = Some(OsTimer::new(time::Duration::from_millis(next.max(1))));
+                self.armed = Some(target);
+            }
+
+            match self.timer.as_mut() {
+                Some(timer) => match Pin::new(timer).poll(ctx) {
+                    task::Poll::Pending => return task::Poll::Pending,
+                    //OS timer fired: loop to cascade the wheel and re-arm.
+                    task::Poll::Ready(()) => {
+                        self.timer = None;
+                        self.armed = None;
+                    }
+                },
+                None => return task::Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Hierarchical timing wheel
+
+use core::task;
+use core::time;
+
+///Number of slots per level. A power of two so that slot selection is a mask.
+const SLOTS: u64 = 64;
+///Bits consumed by one level (`log2(SLOTS)`).
+const BITS: u32 = 6;
+///Mask selecting a slot within a level.
+const MASK: u64 = SLOTS - 1;
+///Number of levels. Six levels of 64 slots span `64^6` milliseconds (~2 years).
+const LEVELS: usize = 6;
+
+///Sentinel standing in for "no entry" in the intrusive list links.
+const NONE: usize = usize::max_value();
+
+///Token identifying a registered timer within the [TimerWheel](struct.TimerWheel.html).
+///
+///Returned by [insert](struct.TimerWheel.html#method.insert) and accepted by
+///[remove](struct.TimerWheel.html#method.remove) for O(1) cancellation.
+///
+///Carries the slab slot's `generation` alongside its index, so a stale token
+///left over from a fired or re-used slot cannot cancel whichever timer now
+///owns that slot.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Token {
+    index: usize,
+    generation: u32,
+}
+
+struct Entry {
+    ///Absolute deadline in milliseconds since the wheel's epoch.
+    deadline: u64,
+    waker: Option<task::Waker>,
+    ///Bumped every time the slot is freed, invalidating outstanding tokens.
+    generation: u32,
+    ///Flat index of the slot currently holding this entry, for O(1) removal.
+    slot: usize,
+    prev: usize,
+    next: usize,
+}
+
+///Hierarchical timing wheel multiplexing many deadlines into a fixed set of slots.
+///
+///Each level holds [SLOTS](constant.SLOTS.html) slots; a timer's level is chosen
+///by the magnitude of its remaining delay and its slot by the relevant bits of
+///the absolute deadline, giving O(1) insert and remove into the intrusive
+///doubly-linked list kept per slot.
+pub struct TimerWheel {
+    ///Current time in milliseconds since epoch; everything below it has fired.
+    now: u64,
+    ///Per-slot list heads, `LEVELS * SLOTS` of them, indexed by `level * SLOTS + slot`.
+    heads: [usize; LEVELS * SLOTS as usize],
+    ///Per-level bitmap of occupied slots, so [next_timeout](#method.next_timeout)
+    ///can skip empty slots with a bit scan instead of walking every chain.
+    occupied: [u64; LEVELS],
+    ///Slab of entries. Vacant entries are threaded through `free` via `next`.
+    entries: Vec<Entry>,
+    free: usize,
+}
+
+fn level_for(delay: u64) -> usize {
+    //Lowest level whose span still contains the delay.
+    for level in 0..LEVELS {
+        if delay >> (BITS * (level as u32 + 1)) == 0 {
+            return level;
+        }
+    }
+    LEVELS - 1
+}
+
+fn slot_index(deadline: u64, level: usize) -> usize {
+    let slot = (deadline >> (BITS * level as u32)) & MASK;
+    level * SLOTS as usize + slot as usize
+}
+
+impl TimerWheel {
+    ///Creates an empty wheel whose epoch corresponds to `now` milliseconds.
+    pub fn new(now: u64) -> Self {
+        Self {
+            now,
+            heads: [NONE; LEVELS * SLOTS as usize],
+            occupied: [0; LEVELS],
+            entries: Vec::new(),
+            free: NONE,
+        }
+    }
+
+    fn alloc(&mut self, mut entry: Entry) -> usize {
+        match self.free {
+            NONE => {
+                entry.generation = 0;
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+            idx => {
+                //Carry the slot's bumped generation over so tokens handed out
+                //for the previous occupant no longer match.
+                entry.generation = self.entries[idx].generation;
+                self.free = self.entries[idx].next;
+                self.entries[idx] = entry;
+                idx
+            }
+        }
+    }
+
+    ///Returns `idx` to the free list, invalidating any token that still names it.
+    fn free_entry(&mut self, idx: usize) {
+        self.entries[idx].waker = None;
+        self.entries[idx].generation = self.entries[idx].generation.wrapping_add(1);
+        self.entries[idx].next = self.free;
+        self.free = idx;
+    }
+
+    fn link(&mut self, idx: usize, slot: usize) {
+        let head = self.heads[slot];
+        self.entries[idx].slot = slot;
+        self.entries[idx].prev = NONE;
+        self.entries[idx].next = head;
+        if head != NONE {
+            self.entries[head].prev = idx;
+        }
+        self.heads[slot] = idx;
+        self.occupied[slot / SLOTS as usize] |= 1 << (slot % SLOTS as usize);
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next, slot) = {
+            let entry = &self.entries[idx];
+            (entry.prev, entry.next, entry.slot)
+        };
+
+        if prev != NONE {
+            self.entries[prev].next = next;
+        } else {
+            self.heads[slot] = next;
+        }
+
+        if next != NONE {
+            self.entries[next].prev = prev;
+        }
+
+        if self.heads[slot] == NONE {
+            self.occupied[slot / SLOTS as usize] &= !(1 << (slot % SLOTS as usize));
+        }
+    }
+
+    ///Registers a timer expiring `deadline` milliseconds since epoch, waking
+    ///`waker` on expiry. Returns a [Token](struct.Token.html) for later removal.
+    pub fn insert(&mut self, deadline: u64, waker: task::Waker) -> Token {
+        let delay = deadline.saturating_sub(self.now);
+        let slot = slot_index(deadline, level_for(delay));
+
+        let idx = self.alloc(Entry {
+            deadline,
+            waker: Some(waker),
+            generation: 0,
+            slot,
+            prev: NONE,
+            next: NONE,
+        });
+        self.link(idx, slot);
+        Token {
+            index: idx,
+            generation: self.entries[idx].generation,
+        }
+    }
+
+    ///Cancels the timer identified by `token`, if still pending.
+    ///
+    ///Tokens whose entry has already fired or been re-used (generation moved on,
+    ///or the slot reclaimed) are ignored, so a stale token can neither unlink a
+    ///live entry nor double-free a vacant one.
+    pub fn remove(&mut self, token: Token) {
+        let idx = token.index;
+        match self.entries.get(idx) {
+            Some(entry) if entry.generation == token.generation && entry.waker.is_some() => (),
+            _ => return,
+        }
+        self.unlink(idx);
+        self.free_entry(idx);
+    }
+
+    ///Refreshes the waker of a still-pending entry in place.
+    ///
+    ///Returns `false` if `token` is stale (the entry fired or was re-used), in
+    ///which case the caller must re-[insert](#method.insert). Avoids the churn of
+    ///removing and re-inserting on every poll when the deadline is unchanged.
+    pub fn update_waker(&mut self, token: Token, waker: &task::Waker) -> bool {
+        match self.entries.get_mut(token.index) {
+            Some(entry) if entry.generation == token.generation && entry.waker.is_some() => {
+                entry.waker = Some(waker.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    ///Milliseconds until the nearest pending deadline, or `None` if empty.
+    ///
+    ///Used by the driver to arm the single backing OS timer. An entry inserted
+    ///earlier at a higher level can fall due before one inserted later at a
+    ///lower level, so every level is scanned; a bit scan over each level's
+    ///occupied slots skips empty slots without touching them.
+    pub fn next_timeout(&self) -> Option<u64> {
+        let mut nearest: Option<u64> = None;
+        for level in 0..LEVELS {
+            let mut bits = self.occupied[level];
+            while bits != 0 {
+                let slot = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+
+                let mut idx = self.heads[level * SLOTS as usize + slot];
+                while idx != NONE {
+                    let entry = &self.entries[idx];
+                    let delay = entry.deadline.saturating_sub(self.now);
+                    nearest = Some(nearest.map_or(delay, |cur| cur.min(delay)));
+                    idx = entry.next;
+                }
+            }
+        }
+        nearest
+    }
+
+    ///Advances the wheel to `now` milliseconds, cascading entries.
+    ///
+    ///Ticks the lowest level one slot at a time over the `[self.now, now)`
+    ///interval; whenever that index wraps to zero the relevant higher-level slot
+    ///is cascaded down, re-bucketing its entries into the levels their shrunken
+    ///delay now fits. Entries reached in a level-0 slot whose deadline has
+    ///arrived have their `Waker` collected into `wakers`. Work is proportional to
+    ///the elapsed slots and the entries cascaded, never the whole slab.
+    pub fn advance(&mut self, now: u64, wakers: &mut Vec<task::Waker>) {
+        while self.now < now {
+            let index = (self.now & MASK) as usize;
+
+            //Wrapping the lowest level: pull the due slot of each higher level
+            //down, stopping at the first level that did not itself wrap.
+            if index == 0 {
+                for level in 1..LEVELS {
+                    let slot = ((self.now >> (BITS * level as u32)) & MASK) as usize;
+                    self.cascade(level, slot);
+                    if slot != 0 {
+                        break;
+                    }
+                }
+            }
+
+            self.now += 1;
+            self.expire(index, now, wakers);
+        }
+    }
+
+    ///Fires or re-buckets every entry sitting in level-0 slot `index`.
+    fn expire(&mut self, index: usize, now: u64, wakers: &mut Vec<task::Waker>) {
+        let mut idx = self.detach(index);
+        while idx != NONE {
+            let next = self.entries[idx].next;
+            let deadline = self.entries[idx].deadline;
+
+            if deadline <= now {
+                if let Some(waker) = self.entries[idx].waker.take() {
+                    wakers.push(waker);
+                }
+                self.free_entry(idx);
+            } else {
+                let slot = slot_index(deadline, level_for(deadline - self.now));
+                self.link(idx, slot);
+            }
+            idx = next;
+        }
+    }
+
+    ///Re-buckets every entry of a higher-level slot into its new lower level.
+    fn cascade(&mut self, level: usize, slot: usize) {
+        let mut idx = self.detach(level * SLOTS as usize + slot);
+        while idx != NONE {
+            let next = self.entries[idx].next;
+            let deadline = self.entries[idx].deadline;
+            let target = slot_index(deadline, level_for(deadline.saturating_sub(self.now)));
+            self.link(idx, target);
+            idx = next;
+        }
+    }
+
+    ///Detaches a whole slot chain, clearing its head and occupancy bit.
+    fn detach(&mut self, slot: usize) -> usize {
+        let head = self.heads[slot];
+        self.heads[slot] = NONE;
+        self.occupied[slot / SLOTS as usize] &= !(1 << (slot % SLOTS as usize));
+        head
+    }
+}
+
+///Converts a `Duration` offset from epoch into whole milliseconds, rounding up
+///so a timer never fires early.
+pub fn as_millis(duration: time::Duration) -> u64 {
+    let nanos = u64::from(duration.subsec_nanos());
+    duration.as_secs().saturating_mul(1_000).saturating_add((nanos + 999_999) / 1_000_000)
+}
@@ -0,0 +1,59 @@
+//! Periodic Timer
+
+use core::{task, time};
+use core::marker::Unpin;
+
+///Periodic timer that re-arms itself after each expiration.
+///
+///Unlike [Oneshot](../oneshot/trait.Oneshot.html), an `Interval` keeps ticking
+///until cancelled, firing every `interval` duration.
+///
+///## Availability
+///
+///Only the Linux `timerfd` backend is currently implemented, exposed as the
+///alias [Timer](type.Timer.html) when the `tokio_on` feature is enabled on Linux
+///or Android (re-using `itimerspec::it_interval`). On every other target the
+///`Interval` trait is provided without a backing `Timer`; equivalent arming for
+///Windows/Apple/Posix and a portable oneshot re-arm fallback are not yet
+///available.
+///
+///## Missed ticks
+///
+///When the executor cannot poll the timer fast enough, several periods may
+///elapse between polls. Implementations expose the number of expirations via
+///[poll_tick](#tymethod.poll_tick) so callers can account for missed ticks.
+pub trait Interval: Send + Sync + Unpin {
+    ///Creates new instance without actually starting timer.
+    ///
+    ///Timer should start only on first `poll_tick`.
+    fn new(interval: time::Duration) -> Self;
+
+    ///Returns configured period of the interval.
+    fn interval(&self) -> time::Duration;
+
+    ///Returns whether timer is ongoing.
+    fn is_ticking(&self) -> bool;
+
+    ///Cancels ongoing timer, if it is not expired yet.
+    fn cancel(&mut self);
+
+    ///Restarts timer with new interval value, replacing waker.
+    fn restart(&mut self, interval: time::Duration, waker: &task::Waker);
+
+    ///Polls for the next tick.
+    ///
+    ///On `Ready` returns the number of periods that have elapsed since the
+    ///previous successful poll. This is at least `1` and greater than `1` only
+    ///when ticks were missed.
+    fn poll_tick(&mut self, ctx: &mut task::Context) -> task::Poll<core::num::NonZeroU64>;
+}
+
+#[cfg(all(feature = "tokio_on", any(target_os = "linux", target_os = "android")))]
+pub mod timer_fd;
+
+#[cfg(all(feature = "tokio_on", any(target_os = "linux", target_os = "android")))]
+pub use timer_fd::TimerFd;
+
+#[cfg(all(feature = "tokio_on", any(target_os = "linux", target_os = "android")))]
+///Alias to Linux `timerfd` based periodic Timer
+pub type Timer = timer_fd::TimerFd;
@@ -0,0 +1,180 @@
+//! Linux `timerfd` periodic implementation
+
+#[cfg(feature = "no_std")]
+core::compile_error!("no_std is not supported for timerfd implementation");
+
+use crate::std::io;
+use core::num::NonZeroU64;
+use core::pin::Pin;
+use core::{mem, ptr, task, time};
+
+use libc::c_int;
+
+#[cfg(target_os = "android")]
+mod sys {
+    #[repr(C)]
+    pub struct itimerspec {
+        pub it_interval: libc::timespec,
+        pub it_value: libc::timespec,
+    }
+
+    extern "C" {
+        pub fn timerfd_create(clockid: libc::clockid_t, flags: libc::c_int) -> libc::c_int;
+        pub fn timerfd_settime(timerid: libc::c_int, flags: libc::c_int, new_value: *const itimerspec, old_value: *mut itimerspec) -> libc::c_int;
+    }
+
+    pub const TFD_NONBLOCK: libc::c_int = libc::O_NONBLOCK;
+}
+
+#[cfg(not(target_os = "android"))]
+use libc as sys;
+
+struct RawTimer(c_int);
+
+impl RawTimer {
+    fn new() -> Self {
+        let fd = unsafe { sys::timerfd_create(libc::CLOCK_MONOTONIC, sys::TFD_NONBLOCK) };
+
+        os_assert!(fd != -1);
+        Self(fd)
+    }
+
+    fn set(&self, timer: sys::itimerspec) {
+        let ret = unsafe { sys::timerfd_settime(self.0, 0, &timer, ptr::null_mut()) };
+        os_assert!(ret != -1);
+    }
+
+    fn read(&self) -> u64 {
+        let mut read_num = 0u64;
+        match unsafe { libc::read(self.0, &mut read_num as *mut u64 as *mut _, 8) } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                match error.kind() {
+                    io::ErrorKind::WouldBlock => 0,
+                    _ => panic!("Unexpected read error: {}", error),
+                }
+            }
+            _ => read_num,
+        }
+    }
+}
+
+impl mio::Evented for RawTimer {
+    fn register(&self, poll: &mio::Poll, token: mio::Token, interest: mio::Ready, opts: mio::PollOpt) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0).register(poll, token, interest, opts)
+    }
+
+    fn reregister(&self, poll: &mio::Poll, token: mio::Token, interest: mio::Ready, opts: mio::PollOpt) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        mio::unix::EventedFd(&self.0).deregister(poll)
+    }
+}
+
+impl Drop for RawTimer {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+enum State {
+    Init(time::Duration),
+    Running,
+}
+
+fn to_timespec(duration: time::Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: duration.as_secs() as libc::time_t,
+        tv_nsec: libc::suseconds_t::from(duration.subsec_nanos()),
+    }
+}
+
+fn set_timer_value(fd: &RawTimer, interval: time::Duration) {
+    let spec = to_timespec(interval);
+
+    let new_value = sys::itimerspec {
+        it_interval: spec,
+        it_value: spec,
+    };
+
+    fd.set(new_value);
+}
+
+///Linux `timerfd` periodic wrapper
+pub struct TimerFd {
+    fd: tokio::io::PollEvented<RawTimer>,
+    interval: time::Duration,
+    state: State,
+}
+
+impl super::Interval for TimerFd {
+    fn new(interval: time::Duration) -> Self {
+        debug_assert!(!(interval.as_secs() == 0 && interval.subsec_nanos() == 0), "Zero interval makes no sense");
+
+        Self {
+            fd: tokio::io::PollEvented::new(RawTimer::new()).expect("To create PollEvented"),
+            interval,
+            state: State::Init(interval),
+        }
+    }
+
+    fn interval(&self) -> time::Duration {
+        self.interval
+    }
+
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Init(_) => false,
+            State::Running => true,
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.fd.get_mut().set(unsafe { mem::zeroed() });
+        self.state = State::Init(self.interval);
+    }
+
+    fn restart(&mut self, new_value: time::Duration, _: &task::Waker) {
+        debug_assert!(!(new_value.as_secs() == 0 && new_value.subsec_nanos() == 0), "Zero interval makes no sense");
+
+        self.interval = new_value;
+        match &mut self.state {
+            State::Init(ref mut interval) => {
+                *interval = new_value;
+            }
+            State::Running => {
+                set_timer_value(&self.fd.get_ref(), new_value);
+            }
+        }
+    }
+
+    fn poll_tick(&mut self, ctx: &mut task::Context) -> task::Poll<NonZeroU64> {
+        loop {
+            match &self.state {
+                State::Init(ref interval) => {
+                    set_timer_value(self.fd.get_ref(), *interval);
+                    self.state = State::Running;
+                }
+                State::Running => {
+                    match Pin::new(&mut self.fd).poll_read_ready(ctx, mio::Ready::readable()) {
+                        task::Poll::Pending => return task::Poll::Pending,
+                        task::Poll::Ready(ready) => match ready.map(|ready| ready.is_readable()).expect("timerfd cannot be ready") {
+                            true => {
+                                let _ = Pin::new(&mut self.fd).clear_read_ready(ctx, mio::Ready::readable());
+                                match NonZeroU64::new(self.fd.get_mut().read()) {
+                                    //`read` returns the number of expirations since the last poll,
+                                    //i.e. how many periods elapsed, accounting for missed ticks.
+                                    Some(ticks) => return task::Poll::Ready(ticks),
+                                    None => return task::Poll::Pending,
+                                }
+                            }
+                            false => return task::Poll::Pending,
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
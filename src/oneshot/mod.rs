@@ -4,6 +4,25 @@ use core::{task, time};
 use core::marker::Unpin;
 use core::future::Future;
 
+///Clock source against which a timer's deadline is measured.
+///
+///Selects the kernel clock passed to the underlying timer primitive. Not every
+///variant is available on every platform, hence the `cfg` gating.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Clock {
+    ///Monotonic clock that is unaffected by wall-clock adjustments, but does
+    ///not advance while the system is suspended.
+    Monotonic,
+    ///Wall-clock time, subject to `settimeofday`/NTP adjustments.
+    Realtime,
+    ///Like [Monotonic](#variant.Monotonic) but also counts time spent suspended.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Boottime,
+    ///Wall-clock time that is additionally able to wake a suspended system.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    RealtimeAlarm,
+}
+
 ///One-shot timer that expires once
 ///
 ///Trait itself describes `Future` that resolves after `timeout`
@@ -40,12 +59,52 @@ use core::future::Future;
 /// }
 ///
 ///```
+///Clock source against which a timer's deadline is measured.
+///
+///Selects the kernel clock passed to the underlying timer primitive. Not every
+///variant is available on every platform, hence the `cfg` gating.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Clock {
+    ///Monotonic clock that is unaffected by wall-clock adjustments, but does
+    ///not advance while the system is suspended.
+    Monotonic,
+    ///Wall-clock time, subject to `settimeofday`/NTP adjustments.
+    Realtime,
+    ///Like [Monotonic](#variant.Monotonic) but also counts time spent suspended.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Boottime,
+    ///Wall-clock time that is additionally able to wake a suspended system.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    RealtimeAlarm,
+}
+
 pub trait Oneshot: Send + Sync + Unpin + Future<Output=()> {
     ///Creates new instance without actually starting timer.
     ///
     ///Timer should start only on first `Future::poll`
     fn new(timeout: time::Duration) -> Self;
 
+    ///Creates new instance that expires at an absolute `deadline`, expressed as
+    ///the elapsed time since the epoch of `clock`.
+    ///
+    ///Unlike [new](#tymethod.new), which schedules relative to the moment of the
+    ///first poll, this pins the expiration to a fixed point on `clock`. Combined
+    ///with [Clock::Realtime](enum.Clock.html#variant.Realtime) or
+    ///`RealtimeAlarm` it allows scheduling against real-time deadlines and
+    ///surviving system suspend, which `Monotonic` cannot provide.
+    ///
+    ///Timer should start only on first `Future::poll`.
+    ///
+    ///The default implementation ignores `clock` and schedules `deadline` as a
+    ///relative timeout measured from the first poll — a best-effort fallback for
+    ///backends without native absolute-deadline support. Backends that can pin
+    ///the expiration to a point on `clock` (e.g. the Linux `timerfd`) override
+    ///this.
+    fn new_at(clock: Clock, deadline: time::Duration) -> Self {
+        let _ = clock;
+        Self::new(deadline)
+    }
+
     ///Returns whether timer is ongoing.
     ///
     ///Note that if it returns `false` it doesn't mean that `is_expired` will return `true`
@@ -66,12 +125,16 @@ pub trait Oneshot: Send + Sync + Unpin + Future<Output=()> {
 
 mod state;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 pub mod web;
+#[cfg(target_os = "wasi")]
+pub mod wasi;
 #[cfg(windows)]
 pub mod win;
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
 pub mod posix;
+#[cfg(all(not(feature = "tokio_on"), any(target_os = "linux", target_os = "android")))]
+pub mod posix_signal;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub mod apple;
 #[cfg(all(feature = "tokio_on", any(target_os = "linux", target_os = "android")))]
@@ -86,7 +149,11 @@ pub use extra::NeverTimer;
 #[cfg(all(feature = "tokio_on", any(target_os = "linux", target_os = "android")))]
 pub use timer_fd::TimerFd;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(target_os = "wasi")]
+///Alias to WASI based Timer.
+pub type Timer = wasi::WasiTimer;
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
 ///Alias to Web based Timer.
 pub type Timer = web::WebTimer;
 
@@ -0,0 +1,192 @@
+//! Signal-delivery Posix timer
+//!
+//! The [posix](../posix/index.html) backend creates its `timer_create` timer with
+//! a fixed `SIGEV_SIGNAL` notification and offers no control over delivery. This
+//! module exposes the full `timer_create`/`timer_settime` surface so callers on
+//! the non-tokio Posix path can pick the delivery signal and notification kind,
+//! arm a period for recurring delivery, and query `timer_getoverrun` to account
+//! for coalesced expirations — feature parity with the
+//! [timerfd](../timer_fd/index.html) path.
+//!
+//! Coverage is currently limited to Linux and Android: the `SIGEV_THREAD`
+//! delivery path needs the `sigev_notify_function`/`sigev_notify_attributes`
+//! union members, whose layout differs on the BSD/illumos `sigevent`. Extending
+//! this to freebsd/netbsd/illumos requires a per-OS `sigevent` shim.
+
+use crate::std::io;
+use core::{mem, ptr, time};
+
+use libc::{c_int, clockid_t};
+
+mod sys {
+    use libc::{c_int, c_void};
+
+    //`libc::sigevent` does not expose the `SIGEV_THREAD` callback union, so we
+    //mirror the glibc layout here, the same way `timer_fd` declares its own
+    //`itimerspec` where libc lacks one. glibc fixes the whole struct at 64 bytes
+    //(`__SIGEV_MAX_SIZE`) on both widths, so the trailing pad must fill whatever
+    //the preceding fields leave: 8 ints on 64-bit, 11 on 32-bit. Getting this
+    //wrong makes `timer_create` read past the struct on 32-bit.
+    #[cfg(target_pointer_width = "64")]
+    #[repr(C)]
+    pub struct sigevent {
+        pub sigev_value: libc::sigval,
+        pub sigev_signo: c_int,
+        pub sigev_notify: c_int,
+        pub sigev_notify_function: Option<extern "C" fn(libc::sigval)>,
+        pub sigev_notify_attributes: *mut c_void,
+        pub __pad: [c_int; 8],
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[repr(C)]
+    pub struct sigevent {
+        pub sigev_value: libc::sigval,
+        pub sigev_signo: c_int,
+        pub sigev_notify: c_int,
+        pub sigev_notify_function: Option<extern "C" fn(libc::sigval)>,
+        pub sigev_notify_attributes: *mut c_void,
+        pub __pad: [c_int; 11],
+    }
+}
+
+///Notification mechanism used by a [SignalTimer](struct.SignalTimer.html).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Notify {
+    ///Deliver the configured real-time signal to the process on expiry.
+    Signal(c_int),
+    ///Spawn a thread running `function`, receiving the timer's `sigval`, on expiry.
+    Thread(extern "C" fn(libc::sigval)),
+    ///Do not deliver any notification on expiry. The timer still arms and counts
+    ///down — its remaining time can be read with `timer_gettime` — but no signal
+    ///or thread is raised, so nothing accumulates for `timer_getoverrun`.
+    None,
+}
+
+///Builder configuring delivery of a `timer_create`-based timer.
+#[derive(Copy, Clone)]
+pub struct Builder {
+    clock: clockid_t,
+    notify: Notify,
+    value: isize,
+}
+
+impl Builder {
+    ///Creates a builder against `CLOCK_MONOTONIC` that delivers `SIGALRM`.
+    pub fn new() -> Self {
+        Self {
+            clock: libc::CLOCK_MONOTONIC,
+            notify: Notify::Signal(libc::SIGALRM),
+            value: 0,
+        }
+    }
+
+    ///Selects the clock source the timer is measured against.
+    pub fn clock(mut self, clock: clockid_t) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    ///Selects the notification mechanism and, for `Signal`, the signal number.
+    pub fn notify(mut self, notify: Notify) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    ///Sets the `siginfo_t::si_value` payload delivered with the notification.
+    pub fn value(mut self, value: isize) -> Self {
+        self.value = value;
+        self
+    }
+
+    ///Creates the timer, without arming it.
+    pub fn create(self) -> io::Result<SignalTimer> {
+        let mut event: sys::sigevent = unsafe { mem::zeroed() };
+        event.sigev_value = libc::sigval { sival_ptr: self.value as *mut _ };
+
+        match self.notify {
+            Notify::Signal(signo) => {
+                event.sigev_notify = libc::SIGEV_SIGNAL;
+                event.sigev_signo = signo;
+            }
+            Notify::Thread(function) => {
+                event.sigev_notify = libc::SIGEV_THREAD;
+                event.sigev_notify_function = Some(function);
+            }
+            Notify::None => {
+                event.sigev_notify = libc::SIGEV_NONE;
+            }
+        }
+
+        let mut timer: libc::timer_t = unsafe { mem::zeroed() };
+        let ret = unsafe { libc::timer_create(self.clock, &mut event as *mut _ as *mut libc::sigevent, &mut timer) };
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(SignalTimer(timer))
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_timespec(duration: time::Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: duration.as_secs() as libc::time_t,
+        tv_nsec: libc::suseconds_t::from(duration.subsec_nanos()),
+    }
+}
+
+///A `timer_create`-based timer that delivers via signal or thread notification.
+pub struct SignalTimer(libc::timer_t);
+
+impl SignalTimer {
+    ///Arms the timer to first fire after `initial`, then every `interval`.
+    ///
+    ///A zero `interval` arms a one-shot timer; a non-zero `interval` re-arms the
+    ///timer for periodic delivery via `it_interval`.
+    pub fn arm(&self, initial: time::Duration, interval: time::Duration) -> io::Result<()> {
+        let new_value = libc::itimerspec {
+            it_interval: to_timespec(interval),
+            it_value: to_timespec(initial),
+        };
+
+        let ret = unsafe { libc::timer_settime(self.0, 0, &new_value, ptr::null_mut()) };
+        match ret {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    ///Disarms the timer, cancelling any pending delivery.
+    pub fn disarm(&self) -> io::Result<()> {
+        let new_value: libc::itimerspec = unsafe { mem::zeroed() };
+        let ret = unsafe { libc::timer_settime(self.0, 0, &new_value, ptr::null_mut()) };
+        match ret {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    ///Returns how many expirations were coalesced since the last delivery.
+    ///
+    ///Wraps `timer_getoverrun`, letting signal-based callers detect missed ticks
+    ///the same way the timerfd path reports them through its read count.
+    pub fn overrun(&self) -> io::Result<c_int> {
+        let ret = unsafe { libc::timer_getoverrun(self.0) };
+        match ret {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(ret),
+        }
+    }
+}
+
+impl Drop for SignalTimer {
+    fn drop(&mut self) {
+        unsafe { libc::timer_delete(self.0) };
+    }
+}
@@ -24,23 +24,39 @@ mod sys {
     }
 
     pub const TFD_NONBLOCK: libc::c_int = libc::O_NONBLOCK;
+    pub const TFD_TIMER_ABSTIME: libc::c_int = 1;
 }
 
 #[cfg(not(target_os = "android"))]
 use libc as sys;
 
+use super::Clock;
+
+fn clock_id(clock: Clock) -> libc::clockid_t {
+    match clock {
+        Clock::Monotonic => libc::CLOCK_MONOTONIC,
+        Clock::Realtime => libc::CLOCK_REALTIME,
+        Clock::Boottime => libc::CLOCK_BOOTTIME,
+        Clock::RealtimeAlarm => libc::CLOCK_REALTIME_ALARM,
+    }
+}
+
 struct RawTimer(c_int);
 
 impl RawTimer {
     fn new() -> Self {
-        let fd = unsafe { sys::timerfd_create(libc::CLOCK_MONOTONIC, sys::TFD_NONBLOCK) };
+        Self::with_clock(libc::CLOCK_MONOTONIC)
+    }
+
+    fn with_clock(clockid: libc::clockid_t) -> Self {
+        let fd = unsafe { sys::timerfd_create(clockid, sys::TFD_NONBLOCK) };
 
         os_assert!(fd != -1);
         Self(fd)
     }
 
-    fn set(&self, timer: sys::itimerspec) {
-        let ret = unsafe { sys::timerfd_settime(self.0, 0, &timer, ptr::null_mut()) };
+    fn set(&self, timer: sys::itimerspec, flags: c_int) {
+        let ret = unsafe { sys::timerfd_settime(self.0, flags, &timer, ptr::null_mut()) };
         os_assert!(ret != -1);
     }
 
@@ -84,7 +100,10 @@ enum State {
     Running(bool),
 }
 
-fn set_timer_value(fd: &RawTimer, timeout: time::Duration) {
+fn set_timer_value(fd: &RawTimer, timeout: time::Duration, flags: c_int) {
+    //In absolute mode `timeout` is the target point on the timer's clock and is
+    //written straight into `it_value`; in relative mode it is a delay from now.
+    //The `timespec` is built the same way either case; only the flag differs.
     let it_value = libc::timespec {
         tv_sec: timeout.as_secs() as libc::time_t,
         tv_nsec: libc::suseconds_t::from(timeout.subsec_nanos()),
@@ -95,13 +114,14 @@ fn set_timer_value(fd: &RawTimer, timeout: time::Duration) {
         it_value,
     };
 
-    fd.set(new_value);
+    fd.set(new_value, flags);
 }
 
 ///Linux `timerfd` wrapper
 pub struct TimerFd {
     fd: tokio::io::PollEvented<RawTimer>,
     state: State,
+    flags: c_int,
 }
 
 impl super::Oneshot for TimerFd {
@@ -111,6 +131,15 @@ impl super::Oneshot for TimerFd {
         Self {
             fd: tokio::io::PollEvented::new(RawTimer::new()).expect("To create PollEvented"),
             state: State::Init(timeout),
+            flags: 0,
+        }
+    }
+
+    fn new_at(clock: Clock, deadline: time::Duration) -> Self {
+        Self {
+            fd: tokio::io::PollEvented::new(RawTimer::with_clock(clock_id(clock))).expect("To create PollEvented"),
+            state: State::Init(deadline),
+            flags: sys::TFD_TIMER_ABSTIME,
         }
     }
 
@@ -129,19 +158,21 @@ impl super::Oneshot for TimerFd {
     }
 
     fn cancel(&mut self) {
-        self.fd.get_mut().set(unsafe { mem::zeroed() });
+        self.fd.get_mut().set(unsafe { mem::zeroed() }, 0);
     }
 
     fn restart(&mut self, new_value: time::Duration, _: &task::Waker) {
         debug_assert!(!(new_value.as_secs() == 0 && new_value.subsec_nanos() == 0), "Zero timeout makes no sense");
 
+        //`new_value` is a relative timeout, even for a timer made by `new_at`.
+        self.flags = 0;
         match &mut self.state {
             State::Init(ref mut timeout) => {
                 *timeout = new_value;
             }
             State::Running(ref mut is_finished) => {
                 *is_finished = false;
-                set_timer_value(&self.fd.get_ref(), new_value);
+                set_timer_value(&self.fd.get_ref(), new_value, self.flags);
             }
         }
     }
@@ -154,7 +185,7 @@ impl Future for TimerFd {
         loop {
             self.state = match &self.state {
                 State::Init(ref timeout) => {
-                    set_timer_value(self.fd.get_ref(), *timeout);
+                    set_timer_value(self.fd.get_ref(), *timeout, self.flags);
                     State::Running(false)
                 }
                 State::Running(false) => {
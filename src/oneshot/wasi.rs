@@ -0,0 +1,129 @@
+//! WASI `poll_oneoff` implementation
+
+use core::future::Future;
+use core::pin::Pin;
+use core::{task, time};
+
+enum State {
+    Init(time::Duration),
+    Running,
+    Expired,
+}
+
+fn as_nanos(duration: time::Duration) -> u64 {
+    duration.as_secs().saturating_mul(1_000_000_000).saturating_add(u64::from(duration.subsec_nanos()))
+}
+
+fn sleep(timeout: time::Duration) {
+    //A single monotonic-clock subscription with the requested relative timeout.
+    //`poll_oneoff` reports the clock event once the interval elapses.
+    let clock = wasi::SubscriptionClock {
+        id: wasi::CLOCKID_MONOTONIC,
+        timeout: as_nanos(timeout),
+        precision: 0,
+        flags: 0,
+    };
+
+    let subscription = wasi::Subscription {
+        userdata: 0,
+        u: wasi::SubscriptionU {
+            tag: wasi::EVENTTYPE_CLOCK.raw(),
+            u: wasi::SubscriptionUU { clock },
+        },
+    };
+
+    let mut event = core::mem::MaybeUninit::<wasi::Event>::uninit();
+    let ret = unsafe { wasi::poll_oneoff(&subscription, event.as_mut_ptr(), 1) };
+    os_assert!(ret.is_ok());
+}
+
+///WASI timer backed by `poll_oneoff`.
+///
+///Unlike [WebTimer](../web/struct.WebTimer.html), which relies on the browser
+///`setTimeout` API, this targets WASI runtimes by subscribing to the monotonic
+///clock.
+///
+///**Blocking.** WASI (as of preview1) exposes no callback-driven reactor, so the
+///first poll calls `poll_oneoff` synchronously and blocks the calling thread for
+///the entire timeout rather than returning `Pending`. On a single-threaded
+///runtime no other task can make progress while the timer is outstanding. Reach
+///for this only where a hard sleep is acceptable, or drive it on a thread of its
+///own.
+pub struct WasiTimer {
+    state: State,
+}
+
+impl super::Oneshot for WasiTimer {
+    fn new(timeout: time::Duration) -> Self {
+        debug_assert!(!(timeout.as_secs() == 0 && timeout.subsec_nanos() == 0), "Zero timeout makes no sense");
+
+        Self {
+            state: State::Init(timeout),
+        }
+    }
+
+    fn new_at(clock: super::Clock, deadline: time::Duration) -> Self {
+        //WASI has no absolute-deadline subscription, so read the selected clock
+        //now and subscribe to the remaining relative delay. A deadline already in
+        //the past collapses to a zero wait that resolves on the first poll.
+        let id = match clock {
+            super::Clock::Realtime => wasi::CLOCKID_REALTIME,
+            super::Clock::Monotonic => wasi::CLOCKID_MONOTONIC,
+        };
+
+        let now = unsafe { wasi::clock_time_get(id, 0) }.expect("Failed to read WASI clock");
+        let remaining = as_nanos(deadline).saturating_sub(now);
+
+        Self {
+            state: State::Init(time::Duration::from_nanos(remaining)),
+        }
+    }
+
+    fn is_ticking(&self) -> bool {
+        match &self.state {
+            State::Running => true,
+            _ => false,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        match &self.state {
+            State::Expired => true,
+            _ => false,
+        }
+    }
+
+    fn cancel(&mut self) {
+        self.state = State::Expired;
+    }
+
+    fn restart(&mut self, new_value: time::Duration, _: &task::Waker) {
+        debug_assert!(!(new_value.as_secs() == 0 && new_value.subsec_nanos() == 0), "Zero timeout makes no sense");
+
+        self.state = State::Init(new_value);
+    }
+}
+
+impl Future for WasiTimer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut task::Context) -> task::Poll<Self::Output> {
+        match &self.state {
+            State::Init(ref timeout) => {
+                //WASI lacks a callback-driven reactor, so block on `poll_oneoff`
+                //for the full interval (see the type-level note); `ctx` carries no
+                //reactor to register with, hence it is unused.
+                let timeout = *timeout;
+                self.state = State::Running;
+                sleep(timeout);
+                self.state = State::Expired;
+                task::Poll::Ready(())
+            }
+            State::Running => {
+                self.state = State::Expired;
+                task::Poll::Ready(())
+            }
+            State::Expired => task::Poll::Ready(()),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use async_timer::driver::Driver;
+
+use std::time;
+
+#[test]
+fn test_driver_single() {
+    cute_async::runtime::tokio(async {
+        let driver = Driver::new();
+        tokio::spawn(driver.run());
+
+        let work = driver.timer(time::Duration::from_millis(500));
+
+        let before = time::SystemTime::now();
+        work.await;
+        let after = time::SystemTime::now();
+        let diff = after.duration_since(before).unwrap();
+
+        assert!(diff.as_millis() >= 250 && diff.as_millis() <= 750);
+    });
+}
+
+#[test]
+fn test_driver_tons_share_one_fd() {
+    cute_async::runtime::tokio(async {
+        const NUM: usize = 1024;
+        let driver = Driver::new();
+        tokio::spawn(driver.run());
+
+        let mut jobs = Vec::with_capacity(NUM);
+        for _ in 0..NUM {
+            jobs.push(driver.timer(time::Duration::from_secs(2)));
+        }
+
+        let before = time::SystemTime::now();
+        futures_util::future::join_all(jobs).await;
+        let after = time::SystemTime::now();
+        let diff = after.duration_since(before).unwrap();
+
+        assert!(diff.as_millis() >= 1_500 && diff.as_millis() <= 2_500);
+    });
+}
@@ -0,0 +1,40 @@
+use async_timer::interval::{Interval, Timer};
+
+use std::time;
+
+use futures_util::future::poll_fn;
+
+#[test]
+fn test_interval_tick() {
+    cute_async::runtime::tokio(async {
+        let mut interval = Timer::new(time::Duration::from_millis(250));
+        assert_eq!(interval.interval(), time::Duration::from_millis(250));
+        assert!(!interval.is_ticking());
+
+        let before = time::SystemTime::now();
+        let ticks = poll_fn(|cx| interval.poll_tick(cx)).await;
+        let after = time::SystemTime::now();
+
+        assert_eq!(ticks.get(), 1);
+        assert!(interval.is_ticking());
+
+        let diff = after.duration_since(before).unwrap();
+        assert!(diff.as_millis() >= 125 && diff.as_millis() <= 500);
+    });
+}
+
+#[test]
+fn test_interval_rearm() {
+    cute_async::runtime::tokio(async {
+        let mut interval = Timer::new(time::Duration::from_millis(250));
+
+        let before = time::SystemTime::now();
+        poll_fn(|cx| interval.poll_tick(cx)).await;
+        //The timer re-arms itself, so a second tick elapses without restarting.
+        poll_fn(|cx| interval.poll_tick(cx)).await;
+        let after = time::SystemTime::now();
+
+        let diff = after.duration_since(before).unwrap();
+        assert!(diff.as_millis() >= 375 && diff.as_millis() <= 1_000);
+    });
+}
@@ -1,4 +1,4 @@
-use async_timer::oneshot::{Oneshot, Timer};
+use async_timer::oneshot::{Clock, Oneshot, Timer};
 
 use std::time;
 
@@ -59,3 +59,34 @@ fn test_nano_oneshot() {
         work.await;
     });
 }
+
+#[test]
+fn test_oneshot_at_realtime() {
+    cute_async::runtime::tokio(async {
+        let deadline = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap() + time::Duration::from_millis(500);
+        let work = Timer::new_at(Clock::Realtime, deadline);
+        assert!(!work.is_expired());
+
+        let before = time::SystemTime::now();
+        work.await;
+        let after = time::SystemTime::now();
+        let diff = after.duration_since(before).unwrap();
+
+        assert!(diff.as_millis() >= 250 && diff.as_millis() <= 750);
+    });
+}
+
+#[test]
+fn test_oneshot_at_realtime_past() {
+    cute_async::runtime::tokio(async {
+        //A deadline already in the past must fire promptly, not wait forever.
+        let deadline = time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap();
+        let work = Timer::new_at(Clock::Realtime, deadline);
+
+        let before = time::SystemTime::now();
+        work.await;
+        let after = time::SystemTime::now();
+
+        assert!(after.duration_since(before).unwrap().as_millis() <= 500);
+    });
+}